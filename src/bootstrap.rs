@@ -1,96 +1,220 @@
-use parallel;
-//use std::{cmp, comm, mem, os, ptr, raw};
-use std::{os, ptr};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use {Bootstrap, Distribution};
 use resamples::Resamples;
 
-impl<T: Clone + Sync> Bootstrap for [T] {
-    fn bootstrap<A: Send>(&self, statistic: fn(&[T]) -> A, nresamples: uint) -> Distribution<A> {
-        // FIXME `RUST_THREADS` should be favored over `num_cpus`
-        let ncpus = os::num_cpus();
-
-        // TODO Under what conditions should multi thread by favored?
-        if ncpus > 1 && nresamples > self.len() {
-            let granularity = nresamples / ncpus + 1;
+impl<T: Clone + Send + Sync> Bootstrap for [T] {
+    fn bootstrap<A: Send>(&self, statistic: fn(&[T]) -> A, nresamples: usize) -> Distribution<A> {
+        #[cfg(feature = "rayon")]
+        fn resample<T: Clone + Send + Sync, A: Send>(
+            sample: &[T],
+            statistic: fn(&[T]) -> A,
+            nresamples: usize,
+        ) -> Vec<A> {
             let mut distribution = Vec::with_capacity(nresamples);
-            unsafe { distribution.set_len(nresamples) }
 
-            parallel::divide(distribution[mut], granularity, |data, _| {
-                let mut resamples = Resamples::new(self);
+            distribution.par_extend((0..nresamples).into_par_iter().map_init(
+                || Resamples::new(sample),
+                |resamples, _| statistic(resamples.next()),
+            ));
+
+            distribution
+        }
 
-                for ptr in data.iter_mut() {
-                    unsafe { ptr::write(ptr, statistic(resamples.next())) }
-                }
-            });
+        #[cfg(not(feature = "rayon"))]
+        fn resample<T: Clone + Sync, A: Send>(
+            sample: &[T],
+            statistic: fn(&[T]) -> A,
+            nresamples: usize,
+        ) -> Vec<A> {
+            let mut resamples = Resamples::new(sample);
 
-            Distribution(distribution)
-        } else {
-            let mut resamples = Resamples::new(self);
+            (0..nresamples).map(|_| statistic(resamples.next())).collect()
+        }
+
+        Distribution(resample(self, statistic, nresamples))
+    }
+}
+
+/// Like `Bootstrap`, but draws resamples from a seeded RNG instead of from entropy
+///
+/// Given the same `seed`, the `Distribution` produced is reproducible regardless of the number
+/// of threads used to compute it.
+pub trait SeededBootstrap<T> {
+    /// Seeded counterpart of `Bootstrap::bootstrap`
+    fn bootstrap_seeded<A: Send>(
+        &self,
+        statistic: fn(&[T]) -> A,
+        nresamples: usize,
+        seed: u64,
+    ) -> Distribution<A>;
+}
+
+impl<T: Clone + Send + Sync> SeededBootstrap<T> for [T] {
+    fn bootstrap_seeded<A: Send>(
+        &self,
+        statistic: fn(&[T]) -> A,
+        nresamples: usize,
+        seed: u64,
+    ) -> Distribution<A> {
+        #[cfg(feature = "rayon")]
+        fn resample<T: Clone + Send + Sync, A: Send>(
+            sample: &[T],
+            statistic: fn(&[T]) -> A,
+            nresamples: usize,
+            seed: u64,
+        ) -> Vec<A> {
+            (0..nresamples).into_par_iter().fold(
+                || (Resamples::with_seed(sample, seed), Vec::new()),
+                |(mut resamples, mut acc), i| {
+                    resamples.reseed(seed.wrapping_add(i as u64));
+                    acc.push(statistic(resamples.next()));
+                    (resamples, acc)
+                },
+            ).map(|(_, acc)| acc).reduce(Vec::new, |mut a, b| { a.extend(b); a })
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        fn resample<T: Clone + Sync, A: Send>(
+            sample: &[T],
+            statistic: fn(&[T]) -> A,
+            nresamples: usize,
+            seed: u64,
+        ) -> Vec<A> {
+            (0..nresamples).map(|i| {
+                let mut resamples = Resamples::with_seed(sample, seed.wrapping_add(i as u64));
 
-            Distribution(range(0, nresamples).map(|_| {
                 statistic(resamples.next())
-            }).collect())
+            }).collect()
         }
+
+        Distribution(resample(self, statistic, nresamples, seed))
     }
 }
 
 /// Returns the bootstrap distribution of the parameter estimated by the 2-sample statistic
 ///
 /// * Bootstrap method: Case resampling
-#[experimental]
 pub fn bootstrap<A: Clone + Sync, B: Clone + Sync, C: Send>(
     first: &[A],
     second: &[B],
     statistic: fn(&[A], &[B]) -> C,
-    nresamples: uint
+    nresamples: usize
 ) -> Distribution<C> {
     assert!(nresamples > 0);
 
-    // FIXME `RUST_THREADS` should be favored over `num_cpus`
-    let ncpus = os::num_cpus();
-    let nresamples_sqrt = (nresamples as f64).sqrt().ceil() as uint;
-    let nresamples = nresamples_sqrt * nresamples_sqrt;
+    let nresamples_sqrt = (nresamples as f64).sqrt().ceil() as usize;
 
-    // TODO Under what conditions should multi thread by favored?
-    if ncpus > 1 && nresamples > first.len() + second.len() {
-        let granularity = nresamples_sqrt / ncpus + 1;
-        let mut distribution = Vec::with_capacity(nresamples);
-        unsafe { distribution.set_len(nresamples) }
+    #[cfg(feature = "rayon")]
+    fn resample<A: Clone + Sync, B: Clone + Sync, C: Send>(
+        first: &[A],
+        second: &[B],
+        statistic: fn(&[A], &[B]) -> C,
+        nresamples_sqrt: usize,
+    ) -> Vec<C> {
+        let mut distribution = Vec::with_capacity(nresamples_sqrt * nresamples_sqrt);
 
-        parallel::divide(distribution[mut], granularity, |data, _| {
+        distribution.par_extend((0..nresamples_sqrt).into_par_iter().flat_map(|_| {
             let mut resamples = Resamples::new(first);
             let mut other_resamples = Resamples::new(second);
+            let resample = resamples.next();
 
-            for chunk in data.chunks_mut(granularity) {
-                let resample = resamples.next();
-
-                for ptr in chunk.iter_mut() {
-                    let other_resample = other_resamples.next();
+            (0..nresamples_sqrt).map(|_| {
+                statistic(resample, other_resamples.next())
+            }).collect::<Vec<_>>().into_par_iter()
+        }));
 
-                    unsafe { ptr::write(ptr, statistic(resample, other_resample)) }
-                }
-            }
-        });
+        distribution
+    }
 
-        Distribution(distribution)
-    } else {
+    #[cfg(not(feature = "rayon"))]
+    fn resample<A: Clone + Sync, B: Clone + Sync, C: Send>(
+        first: &[A],
+        second: &[B],
+        statistic: fn(&[A], &[B]) -> C,
+        nresamples_sqrt: usize,
+    ) -> Vec<C> {
         let mut resamples = Resamples::new(first);
         let mut other_resamples = Resamples::new(second);
-        let mut distribution = Vec::with_capacity(nresamples);
+        let mut distribution = Vec::with_capacity(nresamples_sqrt * nresamples_sqrt);
 
-        for _ in range(0, nresamples_sqrt) {
+        for _ in 0..nresamples_sqrt {
             let resample = resamples.next();
 
-            for _ in range(0, nresamples_sqrt) {
+            for _ in 0..nresamples_sqrt {
                 let other_resample = other_resamples.next();
 
                 distribution.push(statistic(resample, other_resample));
             }
         }
 
-        Distribution(distribution)
+        distribution
     }
+
+    Distribution(resample(first, second, statistic, nresamples_sqrt))
+}
+
+/// Seeded counterpart of `bootstrap`
+///
+/// Given the same `seed`, the `Distribution` produced is reproducible regardless of the number
+/// of threads used to compute it.
+pub fn bootstrap_seeded<A: Clone + Sync, B: Clone + Sync, C: Send>(
+    first: &[A],
+    second: &[B],
+    statistic: fn(&[A], &[B]) -> C,
+    nresamples: usize,
+    seed: u64,
+) -> Distribution<C> {
+    assert!(nresamples > 0);
+
+    let nresamples_sqrt = (nresamples as f64).sqrt().ceil() as usize;
+
+    #[cfg(feature = "rayon")]
+    fn resample<A: Clone + Sync, B: Clone + Sync, C: Send>(
+        first: &[A],
+        second: &[B],
+        statistic: fn(&[A], &[B]) -> C,
+        nresamples_sqrt: usize,
+        seed: u64,
+    ) -> Vec<C> {
+        (0..nresamples_sqrt).into_par_iter().flat_map(|i| {
+            let base = seed.wrapping_add((i * nresamples_sqrt) as u64);
+            let mut resamples = Resamples::with_seed(first, base);
+            let mut other_resamples = Resamples::with_seed(second, base.wrapping_add(1));
+            let resample = resamples.next().to_vec();
+
+            (0..nresamples_sqrt).map(|_| {
+                statistic(&resample, other_resamples.next())
+            }).collect::<Vec<_>>().into_par_iter()
+        }).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn resample<A: Clone + Sync, B: Clone + Sync, C: Send>(
+        first: &[A],
+        second: &[B],
+        statistic: fn(&[A], &[B]) -> C,
+        nresamples_sqrt: usize,
+        seed: u64,
+    ) -> Vec<C> {
+        let mut distribution = Vec::with_capacity(nresamples_sqrt * nresamples_sqrt);
+
+        for i in 0..nresamples_sqrt {
+            let base = seed.wrapping_add((i * nresamples_sqrt) as u64);
+            let mut resamples = Resamples::with_seed(first, base);
+            let mut other_resamples = Resamples::with_seed(second, base.wrapping_add(1));
+            let resample = resamples.next().to_vec();
+
+            for _ in 0..nresamples_sqrt {
+                distribution.push(statistic(&resample, other_resamples.next()));
+            }
+        }
+
+        distribution
+    }
+
+    Distribution(resample(first, second, statistic, nresamples_sqrt, seed))
 }
 
 #[cfg(test)]
@@ -101,7 +225,7 @@ mod test {
     use test;
 
     #[quickcheck]
-    fn bootstrap(size: uint, nresamples: uint) -> TestResult {
+    fn bootstrap(size: usize, nresamples: usize) -> TestResult {
         fn mean(sample: &[f64]) -> f64 {
             sample.mean()
         }
@@ -127,7 +251,7 @@ mod test {
     }
 
     #[quickcheck]
-    fn bootstrap2((size, another_size): (uint, uint), nresamples: uint) -> TestResult {
+    fn bootstrap2((size, another_size): (usize, usize), nresamples: usize) -> TestResult {
         if let (Some(first), Some(second)) =
             (test::vec::<f64>(size), test::vec::<f64>(another_size))
         {
@@ -137,7 +261,7 @@ mod test {
                 return TestResult::discard();
             };
 
-            let nresamples_sqrt = (nresamples as f64).sqrt().ceil() as uint;
+            let nresamples_sqrt = (nresamples as f64).sqrt().ceil() as usize;
             let nresamples = nresamples_sqrt * nresamples_sqrt;
 
             TestResult::from_bool(
@@ -151,6 +275,42 @@ mod test {
         }
 
     }
+
+    #[test]
+    fn bootstrap_seeded_is_reproducible() {
+        use SeededBootstrap;
+
+        fn mean(sample: &[f64]) -> f64 {
+            sample.mean()
+        }
+
+        let sample = [1., 2., 3., 4., 5., 6., 7., 8.];
+        let nresamples = 500;
+        let seed = 0xdead_beef;
+
+        let a = sample[].bootstrap_seeded(mean, nresamples, seed);
+        let b = sample[].bootstrap_seeded(mean, nresamples, seed);
+
+        assert_eq!(a.len(), nresamples);
+        assert_eq!(&a[..], &b[..]);
+    }
+
+    #[test]
+    fn bootstrap2_seeded_is_reproducible() {
+        fn t(a: &[f64], b: &[f64]) -> f64 {
+            a.mean() - b.mean()
+        }
+
+        let first = [1., 2., 3., 4., 5.];
+        let second = [10., 11., 12.];
+        let nresamples = 100;
+        let seed = 0xc0ffee;
+
+        let a = super::bootstrap_seeded(first[], second[], t, nresamples, seed);
+        let b = super::bootstrap_seeded(first[], second[], t, nresamples, seed);
+
+        assert_eq!(&a[..], &b[..]);
+    }
 }
 
 #[cfg(test)]
@@ -161,8 +321,8 @@ mod bench {
     use regression::{Slope, StraightLine};
     use test;
 
-    static NRESAMPLES: uint = 100_000;
-    static SAMPLE_SIZE: uint = 100;
+    static NRESAMPLES: usize = 100_000;
+    static SAMPLE_SIZE: usize = 100;
 
     #[bench]
     fn bootstrap_mean(b: &mut Bencher) {
@@ -202,4 +362,4 @@ mod bench {
             sample[].bootstrap(slr, NRESAMPLES)
         })
     }
-}
\ No newline at end of file
+}
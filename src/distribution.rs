@@ -0,0 +1,122 @@
+//! The bootstrap distribution of a statistic
+
+use std::ops::Deref;
+
+use float::{phi, phi_inv};
+use percentiles::Percentiles;
+
+/// A collection of bootstrap resamples of a statistic
+pub struct Distribution<A>(pub Vec<A>);
+
+impl<A> Deref for Distribution<A> {
+    type Target = Vec<A>;
+
+    fn deref(&self) -> &Vec<A> {
+        &self.0
+    }
+}
+
+impl Distribution<f64> {
+    /// Computes the bias-corrected and accelerated (BCa) confidence interval for `point_estimate`
+    ///
+    /// * `cl`: the confidence level, e.g. `0.95` for a 95% confidence interval
+    /// * `point_estimate`: the statistic computed on the original, full sample
+    /// * `sample`: the original sample the distribution was bootstrapped from
+    /// * `statistic`: the same statistic that produced `point_estimate` and this distribution
+    pub fn confidence_interval<T>(
+        &self,
+        cl: f64,
+        point_estimate: f64,
+        sample: &[T],
+        statistic: fn(&[T]) -> f64,
+    ) -> (f64, f64) where T: Clone {
+        assert!(cl > 0. && cl < 1.);
+
+        let distribution = &self.0;
+        let n = distribution.len() as f64;
+
+        // Bias correction
+        let n_below = distribution.iter().filter(|&&x| x < point_estimate).count() as f64;
+        let z0 = phi_inv(n_below / n);
+
+        // Acceleration, via the jackknife
+        let nloo = sample.len();
+        let mut loo = Vec::with_capacity(nloo);
+
+        for i in 0..nloo {
+            let mut resample = Vec::with_capacity(nloo - 1);
+            resample.extend(sample[..i].iter().cloned());
+            resample.extend(sample[i + 1..].iter().cloned());
+
+            loo.push(statistic(&resample));
+        }
+
+        let mean = loo.iter().fold(0., |acc, &x| acc + x) / nloo as f64;
+        let num = loo.iter().fold(0., |acc, &x| acc + (mean - x).powi(3));
+        let den = 6. * loo.iter().fold(0., |acc, &x| acc + (mean - x).powi(2)).powf(1.5);
+        let a = if den == 0. { 0. } else { num / den };
+
+        let z_lo = phi_inv((1. - cl) / 2.);
+        let z_hi = phi_inv((1. + cl) / 2.);
+
+        let (alpha1, alpha2) = if a == 0. && z0 == 0. {
+            // No bias and no acceleration: the plain percentile interval
+            ((1. - cl) / 2., (1. + cl) / 2.)
+        } else {
+            (
+                phi(z0 + (z0 + z_lo) / (1. - a * (z0 + z_lo))).max(0.).min(1.),
+                phi(z0 + (z0 + z_hi) / (1. - a * (z0 + z_hi))).max(0.).min(1.),
+            )
+        };
+
+        let percentiles = Percentiles::new(distribution);
+
+        (percentiles.at(alpha1 * 100.), percentiles.at(alpha2 * 100.))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Distribution;
+    use percentiles::Percentiles;
+
+    fn mean(sample: &[f64]) -> f64 {
+        sample.iter().fold(0., |acc, &x| acc + x) / sample.len() as f64
+    }
+
+    #[test]
+    fn degenerates_to_the_percentile_interval_when_unbiased_and_unaccelerated() {
+        // All-equal sample: every leave-one-out mean is identical, so the acceleration is zero
+        let sample = [5., 5., 5., 5., 5.];
+        let point_estimate = 5.;
+
+        // Symmetric around, but excluding, the point estimate: exactly half of the bootstrap
+        // replicates fall below it, so the bias correction is zero too
+        let values = vec![1., 2., 3., 4., 6., 7., 8., 9.];
+        let distribution = Distribution(values.clone());
+
+        let cl = 0.90;
+        let (lo, hi) = distribution.confidence_interval(cl, point_estimate, &sample, mean);
+
+        let percentiles = Percentiles::new(&values);
+        let (expected_lo, expected_hi) = (percentiles.at(5.), percentiles.at(95.));
+
+        assert!((lo - expected_lo).abs() < 1e-9);
+        assert!((hi - expected_hi).abs() < 1e-9);
+    }
+
+    #[test]
+    fn confidence_interval_stays_within_the_bootstrap_distribution() {
+        let sample = [1., 2., 3., 4., 5., 6., 7.];
+        let point_estimate = mean(&sample);
+
+        let values: Vec<f64> = (1..200).map(|i| point_estimate - 10. + i as f64 * 0.1).collect();
+        let (min, max) = (values[0], values[values.len() - 1]);
+        let distribution = Distribution(values);
+
+        let (lo, hi) = distribution.confidence_interval(0.95, point_estimate, &sample, mean);
+
+        assert!(lo <= hi);
+        assert!(lo >= min && hi <= max);
+    }
+}
@@ -0,0 +1,102 @@
+//! Standard normal distribution helpers
+
+/// The cumulative distribution function (CDF) of the standard normal distribution
+///
+/// Uses the relation to the complementary error function: `Phi(x) = erfc(-x / sqrt(2)) / 2`
+pub fn phi(x: f64) -> f64 {
+    0.5 * erfc(-x / ::std::f64::consts::SQRT_2)
+}
+
+/// The quantile function (inverse CDF) of the standard normal distribution
+///
+/// Implemented via Peter Acklam's rational approximation, which is accurate to about 1.15e-9.
+pub fn phi_inv(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+        1.383577518672690e+02, -3.066479806614716e+01, 2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+        6.680131188771972e+01, -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+        -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+
+    const P_LOW: f64 = 0.02425;
+    let p_high = 1. - P_LOW;
+
+    if p <= 0. {
+        ::std::f64::NEG_INFINITY
+    } else if p >= 1. {
+        ::std::f64::INFINITY
+    } else if p < P_LOW {
+        let q = (-2. * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+            ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    } else if p <= p_high {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q /
+            (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    } else {
+        let q = (-2. * (1. - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+            ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    }
+}
+
+/// Complementary error function, `erfc(x) = 1 - erf(x)`
+///
+/// Abramowitz and Stegun approximation 7.1.26, accurate to about 1.5e-7.
+fn erfc(x: f64) -> f64 {
+    let sign = if x < 0. { -1. } else { 1. };
+    let x = x.abs();
+
+    let t = 1. / (1. + 0.3275911 * x);
+    let y = 1. - (((((1.061405429 * t - 1.453152027) * t) + 1.421413741) * t - 0.284496736) *
+        t + 0.254829592) * t * (-x * x).exp();
+
+    1. - sign * y
+}
+
+#[cfg(test)]
+mod test {
+    use super::{phi, phi_inv};
+
+    #[test]
+    fn phi_is_one_half_at_zero() {
+        assert!((phi(0.) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn phi_approaches_its_bounds() {
+        assert!(phi(-10.) < 1e-6);
+        assert!(phi(10.) > 1. - 1e-6);
+    }
+
+    #[test]
+    fn phi_inv_is_zero_at_one_half() {
+        assert!(phi_inv(0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn phi_inv_saturates_at_the_extremes() {
+        assert_eq!(phi_inv(0.), ::std::f64::NEG_INFINITY);
+        assert_eq!(phi_inv(1.), ::std::f64::INFINITY);
+    }
+
+    #[test]
+    fn phi_and_phi_inv_are_inverses() {
+        for &p in &[0.025, 0.1, 0.5, 0.9, 0.975] {
+            let x = phi_inv(p);
+
+            assert!((phi(x) - p).abs() < 1e-6);
+        }
+    }
+}
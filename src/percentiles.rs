@@ -0,0 +1,96 @@
+//! Percentiles of a sample (or a bootstrap distribution)
+
+/// A sorted collection of values that supports percentile queries
+///
+/// Percentiles are computed by linear interpolation between the two nearest order statistics,
+/// the same convention used throughout the crate for the bootstrap, BCa intervals and the Tukey
+/// outlier fences.
+pub struct Percentiles(Box<[f64]>);
+
+impl Percentiles {
+    /// Sorts `values` and wraps them for percentile queries
+    ///
+    /// Panics if `values` is empty: there are no percentiles of an empty sample.
+    pub fn new(values: &[f64]) -> Percentiles {
+        assert!(!values.is_empty());
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        Percentiles(sorted.into_boxed_slice())
+    }
+
+    /// Returns the `p`-th percentile, `0 <= p <= 100`
+    pub fn at(&self, p: f64) -> f64 {
+        assert!(p >= 0. && p <= 100.);
+
+        let sorted = &self.0;
+
+        if sorted.len() == 1 {
+            return sorted[0];
+        }
+
+        let rank = p / 100. * (sorted.len() - 1) as f64;
+        let lo = rank.floor() as usize;
+        let hi = rank.ceil() as usize;
+        let weight = rank - lo as f64;
+
+        sorted[lo] * (1. - weight) + sorted[hi] * weight
+    }
+
+    /// Returns the median (50th percentile)
+    pub fn median(&self) -> f64 {
+        self.at(50.)
+    }
+
+    /// Returns `(Q1, median, Q3)`, the 25th, 50th and 75th percentiles
+    pub fn quartiles(&self) -> (f64, f64, f64) {
+        (self.at(25.), self.median(), self.at(75.))
+    }
+
+    /// Returns the inter-quartile range, `Q3 - Q1`
+    pub fn iqr(&self) -> f64 {
+        let (q1, _, q3) = self.quartiles();
+
+        q3 - q1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Percentiles;
+
+    #[test]
+    fn at_0_and_100_return_the_extremes() {
+        let percentiles = Percentiles::new(&[5., 1., 4., 2., 3.]);
+
+        assert_eq!(percentiles.at(0.), 1.);
+        assert_eq!(percentiles.at(100.), 5.);
+    }
+
+    #[test]
+    fn single_element_sample_is_constant_at_every_percentile() {
+        let percentiles = Percentiles::new(&[42.]);
+
+        assert_eq!(percentiles.at(0.), 42.);
+        assert_eq!(percentiles.at(50.), 42.);
+        assert_eq!(percentiles.at(100.), 42.);
+    }
+
+    #[test]
+    fn quartiles_interpolate_between_order_statistics() {
+        let percentiles = Percentiles::new(&[1., 2., 3., 4.]);
+
+        let (q1, median, q3) = percentiles.quartiles();
+        assert_eq!(q1, 1.75);
+        assert_eq!(median, 2.5);
+        assert_eq!(q3, 3.25);
+        assert_eq!(percentiles.iqr(), 1.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics_on_empty_input() {
+        Percentiles::new(&[]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Case resamples drawn with replacement from a sample
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::SmallRng;
+use rand_chacha::ChaChaRng;
+
+/// A lazy sequence of case-resamples drawn with replacement from a `sample`
+///
+/// Each call to `next` overwrites an internal buffer and returns a borrow of it, so the
+/// resampling itself never allocates past the first draw.
+pub struct Resamples<'a, A: 'a, R = SmallRng> {
+    rng: R,
+    sample: &'a [A],
+    buffer: Vec<A>,
+}
+
+impl<'a, A> Resamples<'a, A, SmallRng> where A: Clone {
+    /// Creates a resampler that draws from entropy, i.e. results are not reproducible
+    pub fn new(sample: &'a [A]) -> Resamples<'a, A, SmallRng> {
+        Resamples::with_rng(sample, SmallRng::from_entropy())
+    }
+}
+
+impl<'a, A> Resamples<'a, A, ChaChaRng> where A: Clone {
+    /// Creates a resampler seeded with `seed`, so the sequence of resamples it produces is
+    /// reproducible regardless of how many threads draw from equivalent resamplers
+    pub fn with_seed(sample: &'a [A], seed: u64) -> Resamples<'a, A, ChaChaRng> {
+        Resamples::with_rng(sample, ChaChaRng::seed_from_u64(seed))
+    }
+
+    /// Re-seeds this resampler in place, reusing its buffer
+    ///
+    /// Lets a single `Resamples` be stepped through a deterministic sequence of per-resample
+    /// seeds (e.g. `seed + i` for resample `i`) without re-allocating its buffer each time.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = ChaChaRng::seed_from_u64(seed);
+    }
+}
+
+impl<'a, A, R> Resamples<'a, A, R> where A: Clone, R: Rng {
+    fn with_rng(sample: &'a [A], rng: R) -> Resamples<'a, A, R> {
+        Resamples {
+            rng: rng,
+            sample: sample,
+            buffer: Vec::with_capacity(sample.len()),
+        }
+    }
+
+    /// Draws the next resample, overwriting the internal buffer
+    pub fn next(&mut self) -> &[A] {
+        let n = self.sample.len();
+        let sample = self.sample;
+        let rng = &mut self.rng;
+        let buffer = &mut self.buffer;
+
+        buffer.clear();
+        buffer.extend((0..n).map(|_| sample[rng.gen_range(0..n)].clone()));
+
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Resamples;
+
+    #[test]
+    fn with_seed_is_reproducible() {
+        let sample = [1., 2., 3., 4., 5.];
+
+        let mut a = Resamples::with_seed(&sample, 0xdead_beef);
+        let mut b = Resamples::with_seed(&sample, 0xdead_beef);
+
+        for _ in 0..10 {
+            assert_eq!(a.next(), b.next());
+        }
+    }
+
+    #[test]
+    fn reseed_matches_a_fresh_resampler() {
+        let sample = [1., 2., 3., 4., 5.];
+
+        let mut reused = Resamples::with_seed(&sample, 1);
+        reused.reseed(0xc0ffee);
+
+        let mut fresh = Resamples::with_seed(&sample, 0xc0ffee);
+
+        assert_eq!(reused.next(), fresh.next());
+    }
+}
@@ -0,0 +1,158 @@
+//! Support for bootstrapping several statistics from the same sequence of resamples
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use Distribution;
+use resamples::Resamples;
+
+/// A tuple of statistics, `fn(&[T]) -> A_i`, that can be bootstrapped in a single pass
+pub trait Tuple<T> {
+    /// The tuple of values produced by evaluating every statistic once
+    type Output;
+    /// The tuple of `Distribution`s produced after `nresamples` evaluations
+    type Distributions;
+    /// Accumulates one `Output` per resample into `Distributions`
+    type Builder: Builder<Output=Self::Output, Distributions=Self::Distributions>;
+
+    /// Evaluates every statistic in the tuple against `sample`
+    fn call(&self, sample: &[T]) -> Self::Output;
+}
+
+/// Accumulates the per-resample `Output`s of a `Tuple` of statistics into `Distributions`
+pub trait Builder {
+    /// See `Tuple::Output`
+    type Output;
+    /// See `Tuple::Distributions`
+    type Distributions;
+
+    /// Creates a builder with enough capacity for `nresamples` outputs
+    fn new(nresamples: usize) -> Self;
+    /// Pushes one resample's worth of outputs
+    fn push(&mut self, output: Self::Output);
+    /// Merges another builder's outputs into this one
+    fn extend(&mut self, other: Self);
+    /// Finishes accumulation, returning the tuple of `Distribution`s
+    fn complete(self) -> Self::Distributions;
+}
+
+macro_rules! tuple {
+    ($builder:ident <- ($($t:ident/$i:tt),+)) => {
+        impl<T, $($t: Send),+> Tuple<T> for ($(fn(&[T]) -> $t),+,) {
+            type Output = ($($t),+,);
+            type Distributions = ($(Distribution<$t>),+,);
+            type Builder = $builder<$($t),+>;
+
+            fn call(&self, sample: &[T]) -> Self::Output {
+                ($((self.$i)(sample)),+,)
+            }
+        }
+
+        /// Accumulates the outputs of a tuple of this arity
+        #[allow(non_snake_case)]
+        pub struct $builder<$($t),+> {
+            $($t: Vec<$t>),+
+        }
+
+        #[allow(non_snake_case)]
+        impl<$($t: Send),+> Builder for $builder<$($t),+> {
+            type Output = ($($t),+,);
+            type Distributions = ($(Distribution<$t>),+,);
+
+            fn new(nresamples: usize) -> $builder<$($t),+> {
+                $builder {
+                    $($t: Vec::with_capacity(nresamples)),+
+                }
+            }
+
+            fn push(&mut self, output: Self::Output) {
+                let ($($t),+,) = output;
+
+                $(self.$t.push($t);)+
+            }
+
+            fn extend(&mut self, other: Self) {
+                $(self.$t.extend(other.$t);)+
+            }
+
+            fn complete(self) -> Self::Distributions {
+                ($(Distribution(self.$t)),+,)
+            }
+        }
+    }
+}
+
+tuple!(Builder2 <- (A/0, B/1));
+tuple!(Builder3 <- (A/0, B/1, C/2));
+tuple!(Builder4 <- (A/0, B/1, C/2, D/3));
+
+/// Bootstraps several statistics at once, reusing the same sequence of resamples
+///
+/// This is cheaper than bootstrapping each statistic separately with `[T]::bootstrap`, and
+/// because every statistic sees the exact same resamples, the resulting `Distribution`s stay
+/// correlated — e.g. the distribution of `mean - median` can be computed point-wise afterwards.
+pub fn bootstrap<T, S>(sample: &[T], statistics: S, nresamples: usize) -> S::Distributions
+    where T: Clone + Send + Sync, S: Sync + Tuple<T>, S::Output: Send, S::Builder: Send
+{
+    #[cfg(feature = "rayon")]
+    fn build<T, S>(sample: &[T], statistics: &S, nresamples: usize) -> S::Builder
+        where T: Clone + Send + Sync, S: Sync + Tuple<T>, S::Output: Send, S::Builder: Send
+    {
+        (0..nresamples).into_par_iter().fold(
+            || (Resamples::new(sample), S::Builder::new(0)),
+            |(mut resamples, mut builder), _| {
+                builder.push(statistics.call(resamples.next()));
+                (resamples, builder)
+            },
+        ).map(|(_, builder)| builder).reduce(
+            || S::Builder::new(0),
+            |mut a, b| { a.extend(b); a },
+        )
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn build<T, S>(sample: &[T], statistics: &S, nresamples: usize) -> S::Builder
+        where T: Clone + Sync, S: Tuple<T>
+    {
+        let mut resamples = Resamples::new(sample);
+        let mut builder = S::Builder::new(nresamples);
+
+        for _ in 0..nresamples {
+            builder.push(statistics.call(resamples.next()));
+        }
+
+        builder
+    }
+
+    build(sample, &statistics, nresamples).complete()
+}
+
+#[cfg(test)]
+mod test {
+    use super::bootstrap;
+
+    fn mean(sample: &[f64]) -> f64 {
+        sample.iter().fold(0., |acc, &x| acc + x) / sample.len() as f64
+    }
+
+    fn neg_mean(sample: &[f64]) -> f64 {
+        -mean(sample)
+    }
+
+    #[test]
+    fn bootstrap_shares_resamples_across_statistics() {
+        let sample = [1., 2., 3., 4., 5.];
+        let nresamples = 100;
+
+        let (means, neg_means) = bootstrap(&sample, (mean, neg_mean), nresamples);
+
+        assert_eq!(means.len(), nresamples);
+        assert_eq!(neg_means.len(), nresamples);
+
+        // Both statistics were evaluated against the exact same resamples, so they must be
+        // perfectly (negatively) correlated, point-wise
+        for (&m, &nm) in means.iter().zip(neg_means.iter()) {
+            assert!((m + nm).abs() < 1e-9);
+        }
+    }
+}
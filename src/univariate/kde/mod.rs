@@ -2,10 +2,10 @@
 
 pub mod kernel;
 
-use std::{ptr, thread};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use cast::From;
-use num_cpus;
 use simd::traits::Vector;
 
 use Float;
@@ -38,36 +38,54 @@ impl<'a, A, K> Kde<'a, A, K> where A: 'a + Float, K: Kernel<A> {
 
     /// Maps the KDE over `xs`
     ///
-    /// - Multihreaded
-    pub fn map(&self, xs: &[A]) -> Box<[A]> {
-        let n = xs.len();
-        let ncpus = num_cpus::get();
-
-        // TODO need some sensible threshold to trigger the multi-threaded path
-        if ncpus > 1 && n > ncpus {
-            let granularity = n / ncpus + 1;
-
-            unsafe {
-                let mut ys = Vec::with_capacity(n);
-                ys.set_len(n);
-
-                {
-                    ys.chunks_mut(granularity).enumerate().map(|(i, ys)| {
-                        let offset = i * granularity;
-
-                        thread::scoped(move || {
-                            for (i, y) in ys.iter_mut().enumerate() {
-                                ptr::write(y, (self)(*xs.get_unchecked(offset + i)))
-                            }
-                        })
-                    }).collect::<Vec<_>>();
-                }
+    /// - Multithreaded: when the `rayon` feature is enabled
+    pub fn map(&self, xs: &[A]) -> Box<[A]>
+        where A: Send + Sync, K: Sync
+    {
+        #[cfg(feature = "rayon")]
+        let ys: Vec<A> = xs.par_iter().map(|&x| (self)(x)).collect();
 
-                ys.into_boxed_slice()
-            }
-        } else {
-            xs.iter().map(|&x| (self)(x)).collect::<Vec<_>>().into_boxed_slice()
-        }
+        #[cfg(not(feature = "rayon"))]
+        let ys: Vec<A> = xs.iter().map(|&x| (self)(x)).collect();
+
+        ys.into_boxed_slice()
+    }
+
+    /// Sweeps the KDE over an evenly spaced grid of `npoints`, returning the `(xs, ys)` pair
+    ///
+    /// The grid defaults to `(sample.min() - 3 * h, sample.max() + 3 * h)`, where `h` is the
+    /// estimated bandwidth, but a custom `range` can be supplied instead
+    pub fn sweep(&self, npoints: usize, range: Option<(A, A)>) -> (Box<[A]>, Box<[A]>)
+        where A: Send + Sync, K: Sync
+    {
+        let (start, end) = range.unwrap_or_else(|| {
+            let h = self.bandwidth;
+            let three = A::from(3.);
+
+            (self.sample.min() - three * h, self.sample.max() + three * h)
+        });
+
+        let xs = ::space::linspace::<A>(start, end, npoints).collect::<Vec<_>>().into_boxed_slice();
+        let ys = self.map(&xs);
+
+        (xs, ys)
+    }
+
+    /// Like `sweep`, but additionally evaluates the density at `point`
+    ///
+    /// Returns `(xs, ys, y)` where `y` is the density at `point`
+    pub fn sweep_and_estimate(
+        &self,
+        npoints: usize,
+        range: Option<(A, A)>,
+        point: A,
+    ) -> (Box<[A]>, Box<[A]>, A)
+        where A: Send + Sync, K: Sync
+    {
+        let (xs, ys) = self.sweep(npoints, range);
+        let y = (self)(point);
+
+        (xs, ys, y)
     }
 }
 
@@ -177,6 +195,45 @@ macro_rules! test {
                     TestResult::discard()
                 }
             }
+
+            #[quickcheck]
+            fn sweep(size: usize, start: usize, npoints: usize) -> TestResult {
+                if npoints == 0 {
+                    return TestResult::discard();
+                }
+
+                if let Some(v) = ::test::vec::<$ty>(size, start) {
+                    let slice = &v[start..];
+                    let data = Sample::new(slice);
+                    let kde = Kde::new(data, Gaussian, Bandwidth::Silverman);
+
+                    let (xs, ys) = kde.sweep(npoints, None);
+
+                    TestResult::from_bool(
+                        xs.len() == npoints &&
+                        ys.len() == npoints &&
+                        xs.windows(2).all(|w| w[0] <= w[1])
+                    )
+                } else {
+                    TestResult::discard()
+                }
+            }
+
+            #[quickcheck]
+            fn sweep_and_estimate_matches_call(size: usize, start: usize) -> TestResult {
+                if let Some(v) = ::test::vec::<$ty>(size, start) {
+                    let slice = &v[start..];
+                    let data = Sample::new(slice);
+                    let kde = Kde::new(data, Gaussian, Bandwidth::Silverman);
+                    let point = data.mean();
+
+                    let (_, _, y) = kde.sweep_and_estimate(10, None, point);
+
+                    TestResult::from_bool(approx_eq!(y, kde(point)))
+                } else {
+                    TestResult::discard()
+                }
+            }
         }
     }
 }
@@ -0,0 +1,93 @@
+//! Mixed (pooled) bootstrap for hypothesis testing
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use Distribution;
+use resamples::Resamples;
+
+/// Returns the bootstrap distribution of a 2-sample `statistic` under the null hypothesis that
+/// `first` and `second` are drawn from the same population
+///
+/// * Bootstrap method: Pooled case resampling
+///
+/// Unlike `bivariate::bootstrap`, which resamples `first` and `second` independently, this pools
+/// both samples into a single buffer of length `first.len() + second.len()` and draws each
+/// resample's two groups (of sizes `first.len()` and `second.len()`) from that pool. This
+/// destroys any real difference between the two groups, giving the reference distribution needed
+/// to compute a p-value for `statistic`.
+pub fn bootstrap<A, B>(
+    first: &[A],
+    second: &[A],
+    statistic: fn(&[A], &[A]) -> B,
+    nresamples: usize,
+) -> Distribution<B>
+    where A: Clone + Send + Sync, B: Send
+{
+    let n = first.len();
+    let m = second.len();
+
+    let mut pooled = Vec::with_capacity(n + m);
+    pooled.extend(first.iter().cloned());
+    pooled.extend(second.iter().cloned());
+
+    #[cfg(feature = "rayon")]
+    fn resample<A, B>(pooled: &[A], n: usize, m: usize, statistic: fn(&[A], &[A]) -> B, nresamples: usize) -> Vec<B>
+        where A: Clone + Send + Sync, B: Send
+    {
+        (0..nresamples).into_par_iter().fold(
+            || (Resamples::new(pooled), Vec::new()),
+            |(mut resamples, mut acc), _| {
+                let group = resamples.next();
+                acc.push(statistic(&group[..n], &group[n..n + m]));
+                (resamples, acc)
+            },
+        ).map(|(_, acc)| acc).reduce(Vec::new, |mut a, b| { a.extend(b); a })
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn resample<A, B>(pooled: &[A], n: usize, m: usize, statistic: fn(&[A], &[A]) -> B, nresamples: usize) -> Vec<B>
+        where A: Clone + Sync, B: Send
+    {
+        let mut resamples = Resamples::new(pooled);
+
+        (0..nresamples).map(|_| {
+            let group = resamples.next();
+
+            statistic(&group[..n], &group[n..n + m])
+        }).collect()
+    }
+
+    Distribution(resample(&pooled, n, m, statistic, nresamples))
+}
+
+#[cfg(test)]
+mod test {
+    use super::bootstrap;
+
+    fn mean(sample: &[f64]) -> f64 {
+        sample.iter().fold(0., |acc, &x| acc + x) / sample.len() as f64
+    }
+
+    fn diff_means(a: &[f64], b: &[f64]) -> f64 {
+        mean(a) - mean(b)
+    }
+
+    #[test]
+    fn bootstrap_produces_nresamples_values_bounded_by_the_pool() {
+        let first = [1., 2., 3., 4., 5.];
+        let second = [10., 20., 30.];
+        let nresamples = 200;
+
+        let distribution = bootstrap(&first, &second, diff_means, nresamples);
+
+        assert_eq!(distribution.len(), nresamples);
+
+        let (min, max) = (1., 30.);
+        let spread = max - min;
+
+        // Every resample's statistic is a difference of two means of values drawn from the
+        // pool, so it can't exceed the pool's own spread
+        assert!(distribution.iter().all(|&d| d.abs() <= spread));
+    }
+}
@@ -0,0 +1,138 @@
+//! Classification of outliers
+
+use percentiles::Percentiles;
+use univariate::Sample;
+
+/// A classification of the points of a sample relative to its inter-quartile range
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Label {
+    /// More than 3 times the IQR below the 25th percentile
+    LowSevere,
+    /// Between 1.5 and 3 times the IQR below the 25th percentile
+    LowMild,
+    /// Within 1.5 times the IQR of the 25th/75th percentiles
+    NotAnOutlier,
+    /// Between 1.5 and 3 times the IQR above the 75th percentile
+    HighMild,
+    /// More than 3 times the IQR above the 75th percentile
+    HighSevere,
+}
+
+/// A classified sample, alongside the fences used to classify it
+pub struct LabeledSample<'a, A: 'a> {
+    fences: (A, A, A, A),
+    sample: &'a [A],
+    labels: Box<[Label]>,
+}
+
+impl<'a, A> LabeledSample<'a, A> {
+    /// Returns the number of points in each outlier category
+    ///
+    /// Returns `(low-severe, low-mild, not-an-outlier, high-mild, high-severe)`
+    pub fn count(&self) -> (usize, usize, usize, usize, usize) {
+        let (mut los, mut lom, mut noa, mut him, mut his) = (0, 0, 0, 0, 0);
+
+        for label in self.labels.iter() {
+            match *label {
+                Label::LowSevere => los += 1,
+                Label::LowMild => lom += 1,
+                Label::NotAnOutlier => noa += 1,
+                Label::HighMild => him += 1,
+                Label::HighSevere => his += 1,
+            }
+        }
+
+        (los, lom, noa, him, his)
+    }
+
+    /// Returns the fences used to classify the sample: `(low-severe, low-mild, high-mild,
+    /// high-severe)`
+    pub fn fences(&self) -> (A, A, A, A) where A: Copy {
+        self.fences
+    }
+
+    /// Returns the labeled points of the sample
+    pub fn iter(&self) -> ::std::iter::Zip<::std::slice::Iter<A>, ::std::slice::Iter<Label>> {
+        self.sample.iter().zip(self.labels.iter())
+    }
+
+    /// Returns the labels of the points, in the same order as the original sample
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Returns the original, unlabeled sample
+    pub fn as_slice(&self) -> &[A] {
+        self.sample
+    }
+}
+
+/// Classifies all the points in the `sample` using the Tukey method
+///
+/// - Low severe: `x < Q1 - 3 * IQR`
+/// - Low mild: `Q1 - 3 * IQR <= x < Q1 - 1.5 * IQR`
+/// - High mild: `Q3 + 1.5 * IQR < x <= Q3 + 3 * IQR`
+/// - High severe: `x > Q3 + 3 * IQR`
+pub fn tukey<'a>(sample: &'a Sample<f64>) -> LabeledSample<'a, f64> {
+    let percentiles = Percentiles::new(sample.as_slice());
+    let (q1, _, q3) = percentiles.quartiles();
+    let iqr = percentiles.iqr();
+
+    let (lost, lomt, himt, hist) =
+        (q1 - 3. * iqr, q1 - 1.5 * iqr, q3 + 1.5 * iqr, q3 + 3. * iqr);
+
+    let labels = sample.as_slice().iter().map(|&x| {
+        if x < lost {
+            Label::LowSevere
+        } else if x < lomt {
+            Label::LowMild
+        } else if x > hist {
+            Label::HighSevere
+        } else if x > himt {
+            Label::HighMild
+        } else {
+            Label::NotAnOutlier
+        }
+    }).collect::<Vec<_>>().into_boxed_slice();
+
+    LabeledSample {
+        fences: (lost, lomt, himt, hist),
+        sample: sample.as_slice(),
+        labels: labels,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use univariate::outliers::{self, Label};
+    use univariate::Sample;
+
+    #[test]
+    fn tukey_labels_a_severe_outlier() {
+        // Tightly clustered values plus one point far beyond the high-severe fence
+        let data = [10., 11., 9., 10., 11., 9., 10., 11., 9., 1_000.];
+        let sample = Sample::new(&data);
+        let labeled = outliers::tukey(sample);
+
+        assert_eq!(labeled.as_slice().len(), data.len());
+
+        let (los, lom, noa, him, his) = labeled.count();
+        assert_eq!(los + lom + noa + him + his, data.len());
+        assert_eq!(his, 1);
+
+        let last_label = labeled.labels()[data.len() - 1];
+        assert!(last_label == Label::HighSevere);
+    }
+
+    #[test]
+    fn tukey_fences_are_ordered() {
+        let data = [1., 2., 3., 4., 5., 6., 7., 8., 9.];
+        let sample = Sample::new(&data);
+        let labeled = outliers::tukey(sample);
+
+        let (low_severe, low_mild, high_mild, high_severe) = labeled.fences();
+        assert!(low_severe <= low_mild);
+        assert!(low_mild <= high_mild);
+        assert!(high_mild <= high_severe);
+    }
+}